@@ -0,0 +1,195 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{error::ConsensusError, memory_pool::MemoryPool, ConsensusParameters, MerkleTreeLedger};
+
+use snarkvm_dpc::{
+    testnet1::{instantiated::*, payload::Payload as RecordPayload, record::Record as DPCRecord, DPC},
+    AccountAddress,
+    DPCComponents,
+    DPCScheme,
+    RecordScheme,
+    Storage,
+    TransactionScheme,
+};
+use snarkvm_utilities::{bytes::ToBytes, to_bytes};
+
+use rand::Rng;
+use std::sync::Arc;
+
+/// Fee-per-byte rates are fixed-point, scaled by this factor, so that sub-unit
+/// rates (the common case once fees are a small fraction of a transaction's size)
+/// don't truncate to zero the way a raw integer `fee / size` division would.
+pub const FEE_RATE_SCALE: u64 = 1_000_000;
+
+/// A single block's contribution to a `get_fee_history` response.
+#[derive(Debug, Clone)]
+pub struct FeeHistoryEntry {
+    pub height: u32,
+    pub total_fees: u64,
+    pub block_fullness: f64,
+    /// Fee-per-byte at each requested percentile, scaled by `FEE_RATE_SCALE`.
+    pub fee_per_byte_percentiles: Vec<u64>,
+}
+
+/// The result of `Consensus::get_fee_history`: one entry per inspected block,
+/// oldest to newest, plus a suggested base fee for the next block.
+#[derive(Debug, Clone)]
+pub struct FeeHistory {
+    pub entries: Vec<FeeHistoryEntry>,
+    /// A simple suggestion for the next block's fee-per-byte (scaled by
+    /// `FEE_RATE_SCALE`): the median of the most recent block's percentile
+    /// samples, or `0` if no blocks were inspected.
+    pub suggested_base_fee_per_byte: u64,
+}
+
+/// The shared consensus state for a node: the tunable rules, the DPC's public
+/// parameters, the ledger it's validating blocks against, and the pending
+/// transaction pool.
+pub struct Consensus<S: Storage> {
+    pub parameters: ConsensusParameters,
+    pub public_parameters: <InstantiatedDPC as DPCScheme<MerkleTreeLedger<S>>>::NetworkParameters,
+    pub ledger: Arc<MerkleTreeLedger<S>>,
+    pub memory_pool: MemoryPool<Tx>,
+}
+
+impl<S: Storage> Consensus<S> {
+    /// Builds a transaction spending `old_records`, paying `fee` to whoever mines the
+    /// block that includes it. Outputs must sum to `inputs - fee` (the caller folds
+    /// the spent `amount` and any change into `new_values` already); the fee is the
+    /// slack the caller chose not to claim back, and is recorded on the returned
+    /// `Tx` so the miner can credit it into the coinbase and so fee-history/RPC
+    /// reporting can read it back without re-deriving it from record values.
+    ///
+    /// The balance check only applies when `old_records` are real, spendable
+    /// records. A coinbase (or the genesis tool's initial mint) spends only dummy
+    /// records — which always carry value `0` — to create value out of nothing, so
+    /// there's nothing for those outputs to balance against.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_transaction<R: Rng>(
+        &self,
+        old_records: Vec<DPCRecord<Components>>,
+        old_account_private_keys: Vec<<Components as DPCComponents>::AccountPrivateKey>,
+        new_record_owners: Vec<AccountAddress<Components>>,
+        new_birth_program_ids: Vec<Vec<u8>>,
+        new_death_program_ids: Vec<Vec<u8>>,
+        new_is_dummy_flags: Vec<bool>,
+        new_values: Vec<u64>,
+        new_payloads: Vec<RecordPayload>,
+        memo: [u8; 32],
+        fee: u64,
+        rng: &mut R,
+    ) -> Result<(Vec<DPCRecord<Components>>, Tx), ConsensusError> {
+        let is_minting = old_records.iter().all(|record| record.is_dummy());
+
+        if !is_minting {
+            let input_value: u64 = old_records.iter().map(|record| record.value()).sum();
+            let output_value: u64 = new_values.iter().sum();
+
+            if input_value.checked_sub(output_value) != Some(fee) {
+                return Err(ConsensusError::Message(format!(
+                    "inputs ({}) do not equal outputs ({}) plus the {} fee",
+                    input_value, output_value, fee
+                )));
+            }
+        }
+
+        let (records, mut transaction) = DPC::execute(
+            &self.public_parameters,
+            &old_records,
+            &old_account_private_keys,
+            &new_record_owners,
+            &new_birth_program_ids,
+            &new_death_program_ids,
+            &new_is_dummy_flags,
+            &new_values,
+            &new_payloads,
+            memo,
+            self.parameters.network_id,
+            &*self.ledger,
+            rng,
+        )?;
+
+        transaction.set_fee(fee);
+
+        Ok((records, transaction))
+    }
+
+    /// Walks the last `n_blocks` blocks of `self.ledger` (clamped to the chain's
+    /// current height) and reports, per block oldest to newest: the total fees
+    /// collected, how full the block was relative to its height's
+    /// `max_block_size`, and the requested fee-per-byte `percentiles` (each in
+    /// `[0.0, 1.0]`), scaled by `FEE_RATE_SCALE` to avoid integer-division
+    /// truncation. The suggested base fee is read off the most recent block's
+    /// percentile samples.
+    pub fn get_fee_history(&self, n_blocks: u32, percentiles: &[f64]) -> Result<FeeHistory, ConsensusError> {
+        let tip_height = self.ledger.get_current_block_height();
+        let n_blocks = n_blocks.min(tip_height + 1);
+        let start_height = tip_height + 1 - n_blocks;
+
+        let mut entries = Vec::with_capacity(n_blocks as usize);
+        for height in start_height..=tip_height {
+            let block = self.ledger.get_block_from_block_number(height)?;
+            let max_block_size = self.parameters.active_rules(height).max_block_size;
+
+            let mut fee_rates = Vec::with_capacity(block.transactions.len());
+            let mut total_fees = 0u64;
+            let mut block_size = 0usize;
+
+            for transaction in block.transactions.iter() {
+                let size = to_bytes![transaction]?.len();
+                block_size += size;
+
+                let fee = transaction.fee();
+                total_fees += fee;
+                if size > 0 {
+                    fee_rates.push(fee.saturating_mul(FEE_RATE_SCALE) / size as u64);
+                }
+            }
+            fee_rates.sort_unstable();
+
+            let fee_per_byte_percentiles = percentiles
+                .iter()
+                .map(|percentile| {
+                    if fee_rates.is_empty() {
+                        0
+                    } else {
+                        let index = (((fee_rates.len() - 1) as f64) * percentile.clamp(0.0, 1.0)).round() as usize;
+                        fee_rates[index]
+                    }
+                })
+                .collect();
+
+            entries.push(FeeHistoryEntry {
+                height,
+                total_fees,
+                block_fullness: block_size as f64 / max_block_size.max(1) as f64,
+                fee_per_byte_percentiles,
+            });
+        }
+
+        let suggested_base_fee_per_byte = entries
+            .last()
+            .and_then(|entry| entry.fee_per_byte_percentiles.get(entry.fee_per_byte_percentiles.len() / 2))
+            .copied()
+            .unwrap_or(0);
+
+        Ok(FeeHistory {
+            entries,
+            suggested_base_fee_per_byte,
+        })
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::fork_schedule::{ConsensusRuleSet, ForkSchedule};
+
+use snarkvm_dpc::Network;
+use snarkvm_posw::PoswMarlin;
+
+/// The effective consensus rules at a given height, borrowed from whichever source
+/// (the base parameters, or a scheduled fork) is active there.
+#[derive(Debug, Clone, Copy)]
+pub struct ActiveConsensusRules<'a> {
+    pub max_block_size: usize,
+    pub target_block_time: i64,
+    pub authorized_inner_snark_ids: &'a [Vec<u8>],
+}
+
+/// The tunable consensus rules for a network.
+///
+/// `fork_schedule` lets `max_block_size`, `target_block_time`, and the authorized
+/// inner-SNARK set change at predetermined heights, so the network can stage
+/// upgrades instead of requiring every node to hard-restart in lockstep. Use
+/// `active_rules(height)` rather than reading the base fields directly wherever a
+/// specific block's rules matter (validation, block assembly).
+pub struct ConsensusParameters {
+    pub max_block_size: usize,
+    pub max_nonce: u32,
+    pub target_block_time: i64,
+    pub network_id: Network,
+    pub verifier: PoswMarlin,
+    pub authorized_inner_snark_ids: Vec<Vec<u8>>,
+    pub fork_schedule: ForkSchedule,
+}
+
+impl ConsensusParameters {
+    /// Returns the rules active at `height`: the scheduled fork entry with the
+    /// greatest `activation_height <= height`, falling back to the base parameters
+    /// if no fork has activated yet. `receive_block` and block verification should
+    /// validate each block against the rules active at *its own* height, so that
+    /// old blocks remain valid under the rules that were active when they were mined.
+    pub fn active_rules(&self, height: u32) -> ActiveConsensusRules<'_> {
+        match self.fork_schedule.active_rules(height) {
+            Some(ConsensusRuleSet {
+                max_block_size,
+                target_block_time,
+                authorized_inner_snark_ids,
+            }) => ActiveConsensusRules {
+                max_block_size: *max_block_size,
+                target_block_time: *target_block_time,
+                authorized_inner_snark_ids,
+            },
+            None => ActiveConsensusRules {
+                max_block_size: self.max_block_size,
+                target_block_time: self.target_block_time,
+                authorized_inner_snark_ids: &self.authorized_inner_snark_ids,
+            },
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkvm_dpc::{DPCError, LedgerError};
+
+use std::fmt;
+
+/// The error type returned by the consensus layer.
+#[derive(Debug)]
+pub enum ConsensusError {
+    Message(String),
+    DPCError(DPCError),
+    LedgerError(LedgerError),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for ConsensusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsensusError::Message(message) => write!(f, "{}", message),
+            ConsensusError::DPCError(error) => write!(f, "{}", error),
+            ConsensusError::LedgerError(error) => write!(f, "{}", error),
+            ConsensusError::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ConsensusError {}
+
+impl From<DPCError> for ConsensusError {
+    fn from(error: DPCError) -> Self {
+        ConsensusError::DPCError(error)
+    }
+}
+
+impl From<LedgerError> for ConsensusError {
+    fn from(error: LedgerError) -> Self {
+        ConsensusError::LedgerError(error)
+    }
+}
+
+impl From<std::io::Error> for ConsensusError {
+    fn from(error: std::io::Error) -> Self {
+        ConsensusError::Io(error)
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+/// The subset of `ConsensusParameters` that a network upgrade is allowed to change.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsensusRuleSet {
+    pub max_block_size: usize,
+    pub target_block_time: i64,
+    pub authorized_inner_snark_ids: Vec<Vec<u8>>,
+}
+
+/// A sorted list of height-activated rule changes, so the network can evolve
+/// block size, block time, and the authorized inner-SNARK set at predetermined
+/// heights instead of requiring a hard restart.
+#[derive(Debug, Clone, Default)]
+pub struct ForkSchedule {
+    /// Kept sorted ascending by `activation_height` so `active_rules` can scan from
+    /// the back for the first entry that's already active.
+    entries: Vec<(u32, ConsensusRuleSet)>,
+}
+
+impl ForkSchedule {
+    /// Builds a schedule from `(activation_height, rules)` pairs.
+    pub fn new(mut entries: Vec<(u32, ConsensusRuleSet)>) -> Self {
+        entries.sort_by_key(|(activation_height, _)| *activation_height);
+        Self { entries }
+    }
+
+    /// Returns the scheduled rule set active at `height`: the entry with the
+    /// greatest `activation_height <= height`, or `None` if `height` comes before
+    /// every scheduled entry (the base `ConsensusParameters` apply as-is).
+    pub fn active_rules(&self, height: u32) -> Option<&ConsensusRuleSet> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(activation_height, _)| *activation_height <= height)
+            .map(|(_, rules)| rules)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(max_block_size: usize) -> ConsensusRuleSet {
+        ConsensusRuleSet {
+            max_block_size,
+            target_block_time: 10,
+            authorized_inner_snark_ids: vec![],
+        }
+    }
+
+    #[test]
+    fn picks_the_latest_activated_entry() {
+        let schedule = ForkSchedule::new(vec![(100, rules(2_000)), (0, rules(1_000)), (200, rules(3_000))]);
+
+        assert_eq!(schedule.active_rules(0).unwrap().max_block_size, 1_000);
+        assert_eq!(schedule.active_rules(99).unwrap().max_block_size, 1_000);
+        assert_eq!(schedule.active_rules(100).unwrap().max_block_size, 2_000);
+        assert_eq!(schedule.active_rules(150).unwrap().max_block_size, 2_000);
+        assert_eq!(schedule.active_rules(200).unwrap().max_block_size, 3_000);
+        assert_eq!(schedule.active_rules(1_000).unwrap().max_block_size, 3_000);
+    }
+
+    #[test]
+    fn returns_none_before_the_first_entry() {
+        let schedule = ForkSchedule::new(vec![(100, rules(2_000))]);
+        assert!(schedule.active_rules(0).is_none());
+        assert!(schedule.active_rules(99).is_none());
+    }
+}
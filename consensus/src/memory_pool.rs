@@ -0,0 +1,43 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use parking_lot::RwLock;
+
+/// The set of transactions waiting to be included in a block, keyed by nothing in
+/// particular beyond insertion order; `Miner::establish_block` is what imposes an
+/// order (by fee-per-byte) when it pulls candidates out.
+#[derive(Debug, Default)]
+pub struct MemoryPool<T> {
+    transactions: RwLock<Vec<T>>,
+}
+
+impl<T: Clone> MemoryPool<T> {
+    pub fn insert(&self, transaction: T) {
+        self.transactions.write().push(transaction);
+    }
+
+    pub fn remove(&self, transaction: &T)
+    where
+        T: PartialEq,
+    {
+        self.transactions.write().retain(|candidate| candidate != transaction);
+    }
+
+    /// Snapshots every transaction currently sitting in the pool.
+    pub fn transactions(&self) -> Vec<T> {
+        self.transactions.read().clone()
+    }
+}
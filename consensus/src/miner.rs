@@ -0,0 +1,189 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{error::ConsensusError, Consensus};
+
+use snarkvm_algorithms::CRH;
+use snarkvm_dpc::{
+    block::Transactions as DPCTransactions,
+    block_header::BlockHeader,
+    testnet1::{instantiated::*, payload::Payload as RecordPayload, record::Record as DPCRecord, DPC},
+    Account,
+    AccountAddress,
+    DPCComponents,
+    DPCScheme,
+    Storage,
+    TransactionScheme,
+};
+use snarkvm_utilities::{bytes::ToBytes, to_bytes};
+
+use rand::thread_rng;
+use std::sync::Arc;
+
+/// Mines new blocks on top of `consensus`'s ledger, crediting `address` with the
+/// coinbase (block subsidy plus every included transaction's fee).
+pub struct Miner<S: Storage> {
+    pub address: AccountAddress<Components>,
+    pub consensus: Arc<Consensus<S>>,
+}
+
+impl<S: Storage> Miner<S> {
+    pub fn new(address: AccountAddress<Components>, consensus: Arc<Consensus<S>>) -> Self {
+        Self { address, consensus }
+    }
+
+    /// Builds the next block's transaction set: the memory pool (plus whatever the
+    /// caller passes in `local_transactions`) is sorted by fee-per-byte, highest
+    /// first, and packed in greedily until the block at this height's
+    /// `max_block_size` (via `ConsensusParameters::active_rules`) is reached. This is
+    /// a greedy knapsack approximation, not an optimal solution — a transaction that
+    /// doesn't fit is skipped rather than swapped for a combination of smaller ones,
+    /// but it's a simple, single-pass way to prioritize the most valuable traffic
+    /// under congestion.
+    ///
+    /// The coinbase (subsidy plus the total of every selected transaction's fee) is
+    /// minted and prepended to the returned set.
+    pub fn establish_block(
+        &self,
+        local_transactions: &DPCTransactions<Tx>,
+    ) -> Result<(BlockHeader, DPCTransactions<Tx>, Vec<DPCRecord<Components>>), ConsensusError> {
+        let previous_block_header = self.consensus.ledger.get_latest_block()?.header.clone();
+        let next_height = self.consensus.ledger.get_current_block_height() + 1;
+        let max_block_size = self.consensus.parameters.active_rules(next_height).max_block_size;
+
+        let mut pool_transactions = self.consensus.memory_pool.transactions();
+        pool_transactions.extend(local_transactions.0.iter().cloned());
+
+        let mut candidates: Vec<(Tx, usize)> = pool_transactions
+            .into_iter()
+            .map(|tx| {
+                let size = to_bytes![tx].map(|bytes| bytes.len()).unwrap_or(usize::MAX);
+                (tx, size)
+            })
+            .collect();
+
+        candidates.sort_by(|(tx_a, size_a), (tx_b, size_b)| {
+            fee_per_byte(tx_b.fee(), *size_b)
+                .partial_cmp(&fee_per_byte(tx_a.fee(), *size_a))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mut selected = Vec::with_capacity(candidates.len());
+        let mut total_size = 0usize;
+        let mut total_fees = 0u64;
+        for (tx, size) in candidates {
+            if total_size.saturating_add(size) > max_block_size {
+                continue;
+            }
+            total_size += size;
+            total_fees += tx.fee();
+            selected.push(tx);
+        }
+
+        let (coinbase_records, coinbase_transaction) = self.create_coinbase_transaction(next_height, total_fees)?;
+        selected.insert(0, coinbase_transaction);
+
+        Ok((previous_block_header, DPCTransactions(selected), coinbase_records))
+    }
+
+    /// Mints the coinbase transaction for a block at `height`: a dummy-input
+    /// transaction whose single real output pays `self.address` the block subsidy
+    /// plus `total_fees`. Mirrors the dummy-input pattern the genesis tooling uses
+    /// to mint the initial supply, since a coinbase has no real inputs to spend.
+    fn create_coinbase_transaction(&self, height: u32, total_fees: u64) -> Result<(Vec<DPCRecord<Components>>, Tx), ConsensusError> {
+        let rng = &mut thread_rng();
+        let reward = block_reward(height) + total_fees;
+
+        let noop_program_vk_hash = self
+            .consensus
+            .public_parameters
+            .system_parameters
+            .program_verification_key_crh
+            .hash(&to_bytes![
+                self.consensus
+                    .public_parameters
+                    .noop_program_snark_parameters
+                    .verification_key
+            ]?)?;
+        let noop_program_id = to_bytes![noop_program_vk_hash]?;
+
+        let dummy_account = Account::new(
+            &self.consensus.public_parameters.system_parameters.account_signature,
+            &self.consensus.public_parameters.system_parameters.account_commitment,
+            &self.consensus.public_parameters.system_parameters.account_encryption,
+            rng,
+        )?;
+
+        let old_account_private_keys = vec![dummy_account.private_key.clone(); Components::NUM_INPUT_RECORDS];
+        let mut old_records = Vec::with_capacity(Components::NUM_INPUT_RECORDS);
+        for i in 0..Components::NUM_INPUT_RECORDS {
+            let old_sn_nonce = self
+                .consensus
+                .public_parameters
+                .system_parameters
+                .serial_number_nonce
+                .hash(&[64u8 + (i as u8); 1])?;
+            old_records.push(DPC::generate_record(
+                &self.consensus.public_parameters.system_parameters,
+                old_sn_nonce,
+                dummy_account.address.clone(),
+                true,
+                0,
+                RecordPayload::default(),
+                noop_program_id.clone(),
+                noop_program_id.clone(),
+                rng,
+            )?);
+        }
+
+        let new_record_owners = vec![self.address.clone(); Components::NUM_OUTPUT_RECORDS];
+        let new_birth_program_ids = vec![noop_program_id.clone(); Components::NUM_OUTPUT_RECORDS];
+        let new_death_program_ids = vec![noop_program_id; Components::NUM_OUTPUT_RECORDS];
+        let new_payloads = vec![RecordPayload::default(); Components::NUM_OUTPUT_RECORDS];
+
+        let mut new_is_dummy_flags = vec![false];
+        new_is_dummy_flags.extend(vec![true; Components::NUM_OUTPUT_RECORDS - 1]);
+
+        let mut new_values = vec![reward];
+        new_values.extend(vec![0; Components::NUM_OUTPUT_RECORDS - 1]);
+
+        let memo: [u8; 32] = rand::Rng::gen(rng);
+
+        Ok(self.consensus.create_transaction(
+            old_records,
+            old_account_private_keys,
+            new_record_owners,
+            new_birth_program_ids,
+            new_death_program_ids,
+            new_is_dummy_flags,
+            new_values,
+            new_payloads,
+            memo,
+            0,
+            rng,
+        )?)
+    }
+}
+
+/// The block subsidy at `height`. A flat placeholder — this checkout doesn't include
+/// the real halving schedule from `snarkos-consensus`'s parameters module.
+fn block_reward(_height: u32) -> u64 {
+    150 * 1_000_000 // 150 credits, in the smallest denomination used elsewhere in this crate
+}
+
+fn fee_per_byte(fee: u64, size: usize) -> f64 {
+    fee as f64 / size.max(1) as f64
+}
@@ -0,0 +1,178 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{Node, Payload};
+
+use snarkos_consensus::error::ConsensusError;
+use snarkvm_algorithms::merkle_tree::MerkleTree;
+use snarkvm_dpc::{block_header::BlockHeader, testnet1::instantiated::CommitmentMerkleParameters, Storage};
+use snarkvm_utilities::{
+    bytes::{FromBytes, ToBytes},
+    to_bytes,
+};
+
+use std::net::SocketAddr;
+
+/// How often (in blocks) a synced node offers a checkpoint to a joining peer.
+/// Checkpoints only ever land on a multiple of this height.
+pub const CHECKPOINT_INTERVAL: u32 = 1_000;
+
+impl<S: Storage + Send + Sync + 'static> Node<S> {
+    /// Builds the `Checkpoint` payload for the checkpoint at or before `height`:
+    /// the commitment Merkle root, plus the frontier a joining node needs to keep
+    /// appending new commitments to the same tree — how many leaves it has so far,
+    /// the rightmost one, and that leaf's authentication path up to the root.
+    /// Shipping only the frontier (rather than every leaf) is what makes this a
+    /// fast-sync shortcut instead of just replaying the whole chain. The header
+    /// chain from genesis up to `height` rides along too, so the receiver can
+    /// check the root's proof-of-work lineage before trusting it.
+    pub fn serve_checkpoint(&self, height: u32) -> Result<Payload, ConsensusError> {
+        let checkpoint_height = (height / CHECKPOINT_INTERVAL) * CHECKPOINT_INTERVAL;
+
+        let leaves = self.consensus.ledger.commitments_up_to(checkpoint_height)?;
+        let last_leaf = leaves
+            .last()
+            .ok_or_else(|| ConsensusError::Message("cannot checkpoint an empty commitment tree".to_string()))?;
+
+        let tree = MerkleTree::<CommitmentMerkleParameters>::new(self.consensus.ledger.merkle_parameters().clone(), &leaves)
+            .map_err(|error| ConsensusError::Message(error.to_string()))?;
+
+        let merkle_root = to_bytes![tree.root()]?;
+
+        // The rightmost leaf's authentication path doubles as the append frontier:
+        // it's exactly the sibling hashes needed to recompute `merkle_root` from
+        // `last_leaf`, without the receiver ever seeing any of the earlier leaves.
+        let frontier_path = tree
+            .generate_proof(leaves.len() - 1, last_leaf)
+            .map_err(|error| ConsensusError::Message(error.to_string()))?;
+
+        let header_chain = (0..=checkpoint_height)
+            .map(|checkpoint_block_height| {
+                self.consensus
+                    .ledger
+                    .get_block_from_block_number(checkpoint_block_height)
+                    .map(|block| block.header)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Payload::Checkpoint {
+            height: checkpoint_height,
+            merkle_root,
+            leaf_count: leaves.len() as u32,
+            last_leaf: to_bytes![last_leaf]?,
+            frontier_path: to_bytes![frontier_path]?,
+            header_chain,
+        })
+    }
+
+    /// Verifies a `Checkpoint` payload received from `remote_address` and, if it
+    /// holds up, seeds `self.consensus.ledger`'s commitment tree from it so normal
+    /// block sync can resume from `height` instead of replaying every block from
+    /// genesis.
+    ///
+    /// A checkpoint is only trustworthy if `merkle_root` is tied to something the
+    /// verified header chain itself committed to — otherwise a peer could present
+    /// a perfectly legitimate, proof-of-work-valid header chain alongside an
+    /// entirely fabricated (but internally self-consistent) commitment tree, and
+    /// this function would have no way to tell. So verification is three steps,
+    /// each gating the next:
+    /// - every header in `header_chain` carries a valid proof-of-work, and each one
+    ///   correctly extends the previous one, all the way from genesis;
+    /// - `merkle_root` matches the commitment root the header at `height` itself
+    ///   commits to, binding the advertised root to the chain we just verified
+    ///   instead of trusting it at face value;
+    /// - replaying `frontier_path` from `last_leaf` through the same tree
+    ///   construction the ledger itself uses reproduces that same `merkle_root`
+    ///   exactly. A peer that can't do that is lying about its commitment set,
+    ///   regardless of how plausible its header chain looks.
+    pub fn apply_checkpoint(
+        &self,
+        remote_address: SocketAddr,
+        height: u32,
+        merkle_root: Vec<u8>,
+        leaf_count: u32,
+        last_leaf: Vec<u8>,
+        frontier_path: Vec<u8>,
+        header_chain: Vec<BlockHeader>,
+    ) -> Result<(), ConsensusError> {
+        if header_chain.len() != height as usize + 1 {
+            return Err(ConsensusError::Message(format!(
+                "checkpoint from {} claims height {} but shipped {} headers",
+                remote_address,
+                height,
+                header_chain.len()
+            )));
+        }
+
+        let genesis_hash = self.consensus.ledger.get_block_from_block_number(0)?.header.get_hash();
+        if header_chain.first().map(|header| header.get_hash()) != Some(genesis_hash) {
+            return Err(ConsensusError::Message(format!(
+                "checkpoint from {} has a header chain that doesn't start at our genesis block",
+                remote_address
+            )));
+        }
+
+        for header in &header_chain {
+            if !header.is_valid_proof_of_work(&self.consensus.parameters.verifier) {
+                return Err(ConsensusError::Message(format!(
+                    "checkpoint from {} includes a header with an invalid proof-of-work",
+                    remote_address
+                )));
+            }
+        }
+        for window in header_chain.windows(2) {
+            if window[1].previous_block_hash != window[0].get_hash() {
+                return Err(ConsensusError::Message(format!(
+                    "checkpoint from {} has a header chain with a broken link",
+                    remote_address
+                )));
+            }
+        }
+
+        // The header at `height` is the tip of the verified chain, and its own
+        // `merkle_root_hash` is what the block actually commits to. If the
+        // advertised `merkle_root` doesn't match that, the checkpoint's commitment
+        // tree isn't the one this header chain attests to, no matter how
+        // self-consistent it looks on its own.
+        let tip_header = header_chain.last().ok_or_else(|| {
+            ConsensusError::Message(format!("checkpoint from {} shipped an empty header chain", remote_address))
+        })?;
+        if to_bytes![tip_header.merkle_root_hash]? != merkle_root {
+            return Err(ConsensusError::Message(format!(
+                "checkpoint from {} advertises a merkle root its own header chain doesn't commit to",
+                remote_address
+            )));
+        }
+
+        let last_leaf = FromBytes::read(&last_leaf[..])?;
+        let frontier_path = FromBytes::read(&frontier_path[..])?;
+        let root = FromBytes::read(&merkle_root[..])?;
+
+        let path_is_valid = frontier_path
+            .verify(self.consensus.ledger.merkle_parameters(), &root, &last_leaf)
+            .map_err(|error| ConsensusError::Message(error.to_string()))?;
+        if !path_is_valid {
+            return Err(ConsensusError::Message(format!(
+                "checkpoint from {} has a frontier that doesn't reproduce its advertised merkle root",
+                remote_address
+            )));
+        }
+
+        self.consensus.ledger.seed_commitment_tree_frontier(merkle_root, leaf_count, last_leaf, frontier_path, height)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{NetworkError, Node};
+
+use snarkvm_objects::Storage;
+
+use std::net::SocketAddr;
+use tokio::net::TcpStream;
+
+impl<S: Storage + Send + Sync + 'static> Node<S> {
+    /// Opens a fresh TCP connection to `remote_address`.
+    ///
+    /// This only establishes the transport-level connection; it doesn't perform the
+    /// handshake or register the peer's outbound channels — callers that need a
+    /// fully usable peer connection (as opposed to `reconnect_with_backoff`'s bare
+    /// retry probe) go through the node's regular connection-handling path for that.
+    pub async fn connect_to(&self, remote_address: SocketAddr) -> Result<TcpStream, NetworkError> {
+        Ok(TcpStream::connect(remote_address).await?)
+    }
+}
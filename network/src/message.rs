@@ -0,0 +1,57 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkvm_dpc::{block::Block, block_header::BlockHeader, testnet1::instantiated::Tx};
+
+/// The body of a single network message, classified into a send priority by
+/// `crate::outbound::Priority::of`.
+#[derive(Debug, Clone)]
+pub enum Payload {
+    /// A mined or relayed block.
+    Block(Block<Tx>),
+    /// A block sent in direct response to a `GetSync`/`GetBlocks` request.
+    SyncBlock(Block<Tx>),
+    /// The block headers a peer has that we don't, in response to `GetSync`.
+    Sync(Vec<BlockHeader>),
+    /// A request for the block hashes a peer has past the given height.
+    GetSync(u32),
+    /// A request for the full blocks matching the given headers.
+    GetBlocks(Vec<BlockHeader>),
+    /// A transaction being relayed through the network.
+    Transaction(Tx),
+    /// The memory pool's current transaction set, in response to `GetMemoryPool`.
+    MemoryPool(Vec<Tx>),
+    /// A request for a peer's current memory pool.
+    GetMemoryPool,
+    /// A liveness/height probe; carries the sender's current block height.
+    Ping(u32),
+    /// A request to fast-sync from a checkpoint at or before the given height.
+    GetCheckpoint(u32),
+    /// A commitment-tree checkpoint at `height`: the tree's root, and the frontier
+    /// needed to keep appending to it without replaying every earlier commitment
+    /// (the number of leaves so far, the rightmost one, and its authentication
+    /// path up to `merkle_root`), plus the header chain from genesis up to
+    /// `height` so the receiver can verify the root's proof-of-work lineage
+    /// before trusting it.
+    Checkpoint {
+        height: u32,
+        merkle_root: Vec<u8>,
+        leaf_count: u32,
+        last_leaf: Vec<u8>,
+        frontier_path: Vec<u8>,
+        header_chain: Vec<BlockHeader>,
+    },
+}
@@ -22,55 +22,282 @@ use tokio::sync::mpsc::error::TrySendError;
 
 use std::{
     net::SocketAddr,
-    sync::atomic::{AtomicU64, Ordering},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
-/// A core data structure for handling outbound network traffic.
+/// The default per-write timeout applied to a single `write_message` call.
+const DEFAULT_WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+/// The initial delay before the first reconnection attempt.
+const INITIAL_RECONNECT_BACKOFF: Duration = Duration::from_secs(1);
+/// The reconnection backoff never grows past this.
+const MAX_RECONNECT_BACKOFF: Duration = Duration::from_secs(64);
+
+/// The relative importance of an outbound message.
+///
+/// Lanes are drained strictly in priority order, so a peer whose `Low` lane is backed
+/// up can't delay a `Block` that's waiting behind it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    /// Blocks and sync responses: these drive consensus forward, so a full lane blocks
+    /// the sender instead of silently dropping the message.
+    Critical,
+    /// Transactions and memory pool traffic.
+    Normal,
+    /// Pings and peer gossip: safe to drop under load, the next one is along shortly.
+    Low,
+}
+
+impl Priority {
+    /// Classifies a `Payload` into the lane it should be queued on.
+    fn of(payload: &Payload) -> Self {
+        match payload {
+            Payload::Block(..)
+            | Payload::SyncBlock(..)
+            | Payload::Sync(..)
+            | Payload::GetSync(..)
+            | Payload::GetBlocks(..)
+            // Checkpoint fast-sync is on the same critical path as regular block sync:
+            // a joining node is blocked on these until it can resume normal sync.
+            | Payload::GetCheckpoint(..)
+            | Payload::Checkpoint { .. } => Priority::Critical,
+            Payload::Transaction(..) | Payload::MemoryPool(..) | Payload::GetMemoryPool => Priority::Normal,
+            _ => Priority::Low,
+        }
+    }
+}
+
+/// The per-peer send counters, shared between a peer's `PeerChannels` (read by
+/// `Node::network_stats` for per-peer reporting) and its `PeerReceivers` (written by
+/// `Node::listen_for_outbound_messages` at write time).
 #[derive(Debug, Default)]
-pub struct Outbound {
-    /// The map of remote addresses to their active write channels.
-    pub(crate) channels: MpmcMap<SocketAddr, Sender>,
-    /// The monotonic counter for the number of send requests that succeeded.
+pub(crate) struct PeerSendCounts {
     send_success_count: AtomicU64,
-    /// The monotonic counter for the number of send requests that failed.
     send_failure_count: AtomicU64,
 }
 
+impl PeerSendCounts {
+    fn snapshot(&self) -> LaneStats {
+        LaneStats {
+            send_success_count: self.send_success_count.load(Ordering::SeqCst),
+            send_failure_count: self.send_failure_count.load(Ordering::SeqCst),
+        }
+    }
+}
+
+/// The set of per-priority send channels kept for a single connected peer.
+#[derive(Debug, Clone)]
+pub struct PeerChannels {
+    pub(crate) critical: Sender,
+    pub(crate) normal: Sender,
+    pub(crate) low: Sender,
+    pub(crate) counts: Arc<PeerSendCounts>,
+}
+
+impl PeerChannels {
+    pub fn new(critical: Sender, normal: Sender, low: Sender, counts: Arc<PeerSendCounts>) -> Self {
+        Self {
+            critical,
+            normal,
+            low,
+            counts,
+        }
+    }
+
+    fn lane(&self, priority: Priority) -> &Sender {
+        match priority {
+            Priority::Critical => &self.critical,
+            Priority::Normal => &self.normal,
+            Priority::Low => &self.low,
+        }
+    }
+}
+
+/// The matching set of per-priority receive channels, owned by the task that drains them.
+#[derive(Debug)]
+pub struct PeerReceivers {
+    pub(crate) critical: Receiver,
+    pub(crate) normal: Receiver,
+    pub(crate) low: Receiver,
+    pub(crate) counts: Arc<PeerSendCounts>,
+}
+
+impl PeerReceivers {
+    pub fn new(critical: Receiver, normal: Receiver, low: Receiver, counts: Arc<PeerSendCounts>) -> Self {
+        Self {
+            critical,
+            normal,
+            low,
+            counts,
+        }
+    }
+}
+
+/// The per-lane send counters for a single priority.
+#[derive(Debug, Default)]
+struct LaneCounts {
+    send_success_count: AtomicU64,
+    send_failure_count: AtomicU64,
+}
+
+impl LaneCounts {
+    fn snapshot(&self) -> LaneStats {
+        LaneStats {
+            send_success_count: self.send_success_count.load(Ordering::SeqCst),
+            send_failure_count: self.send_failure_count.load(Ordering::SeqCst),
+        }
+    }
+}
+
+/// A point-in-time snapshot of outbound send activity for a single priority lane.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LaneStats {
+    pub send_success_count: u64,
+    pub send_failure_count: u64,
+}
+
+/// A point-in-time snapshot of a single connected peer, as known to the network layer.
+#[derive(Debug, Clone)]
+pub struct PeerStats {
+    pub address: SocketAddr,
+    /// The last time any message was received from this peer.
+    pub last_seen: Option<std::time::SystemTime>,
+    /// The peer's most recently negotiated block height, if known.
+    pub block_height: Option<u32>,
+    /// This peer's own send tallies, combined across its three lanes.
+    pub send_success_count: u64,
+    pub send_failure_count: u64,
+}
+
+/// A point-in-time snapshot of this node's network connectivity.
+///
+/// Aggregates the outbound send counters with what the peer book knows about each
+/// connection. Surfaced over RPC (`getnetworkstats`) so operators can
+/// diagnose asymmetric connectivity, e.g. a peer we keep sending to that never
+/// responds. Each peer entry reports its own send tallies alongside the node-wide
+/// lane totals.
+#[derive(Debug, Clone, Default)]
+pub struct NetworkStats {
+    pub connected_peer_count: u16,
+    pub active_handshake_count: u16,
+    pub max_peers: u16,
+    pub critical: LaneStats,
+    pub normal: LaneStats,
+    pub low: LaneStats,
+    pub peers: Vec<PeerStats>,
+}
+
+/// A core data structure for handling outbound network traffic.
+#[derive(Debug)]
+pub struct Outbound {
+    /// The map of remote addresses to their active per-priority write channels.
+    pub(crate) channels: MpmcMap<SocketAddr, PeerChannels>,
+    /// The send counters for the `Critical` lane.
+    critical: LaneCounts,
+    /// The send counters for the `Normal` lane.
+    normal: LaneCounts,
+    /// The send counters for the `Low` lane.
+    low: LaneCounts,
+    /// The timeout applied to each individual `write_message` call.
+    write_timeout: Duration,
+}
+
+impl Default for Outbound {
+    fn default() -> Self {
+        Self::new(Default::default(), DEFAULT_WRITE_TIMEOUT)
+    }
+}
+
 impl Outbound {
-    pub fn new(channels: MpmcMap<SocketAddr, Sender>) -> Self {
+    pub fn new(channels: MpmcMap<SocketAddr, PeerChannels>, write_timeout: Duration) -> Self {
         Self {
             channels,
-            send_success_count: Default::default(),
-            send_failure_count: Default::default(),
+            critical: Default::default(),
+            normal: Default::default(),
+            low: Default::default(),
+            write_timeout,
         }
     }
 
+    /// The combined number of successful sends, across all priority lanes.
+    pub fn send_success_count(&self) -> u64 {
+        self.critical.send_success_count.load(Ordering::SeqCst)
+            + self.normal.send_success_count.load(Ordering::SeqCst)
+            + self.low.send_success_count.load(Ordering::SeqCst)
+    }
+
+    /// The combined number of failed sends, across all priority lanes.
+    pub fn send_failure_count(&self) -> u64 {
+        self.critical.send_failure_count.load(Ordering::SeqCst)
+            + self.normal.send_failure_count.load(Ordering::SeqCst)
+            + self.low.send_failure_count.load(Ordering::SeqCst)
+    }
+
+    fn lane_counts(&self, priority: Priority) -> &LaneCounts {
+        match priority {
+            Priority::Critical => &self.critical,
+            Priority::Normal => &self.normal,
+            Priority::Low => &self.low,
+        }
+    }
+
+    /// Snapshots the send counters for every lane, for reporting over RPC.
+    fn lane_snapshots(&self) -> (LaneStats, LaneStats, LaneStats) {
+        (self.critical.snapshot(), self.normal.snapshot(), self.low.snapshot())
+    }
+
     ///
     /// Sends the given request to the address associated with it.
     ///
     /// Creates or fetches an existing channel with the remote address,
     /// and attempts to send the given request to them.
     ///
+    /// The request is classified into a priority lane first. A full `Low` or `Normal`
+    /// lane drops the request, as before; a full `Critical` lane blocks the caller
+    /// instead, since those messages aren't safe to discard.
+    ///
+    /// This only enqueues the request onto the peer's lane; it doesn't touch the
+    /// lane counters. Those are incremented once, at write time, in
+    /// `Node::listen_for_outbound_messages` — counting here too would double-count
+    /// every message that's later actually written to the socket.
+    ///
     #[inline]
     pub async fn send_request(&self, request: Message) {
         let target_addr = request.receiver();
+        let priority = Priority::of(&request.payload);
+
         // Fetch the outbound channel.
         match self.outbound_channel(target_addr).await {
-            Ok(channel) => match channel.try_send(request) {
-                Ok(()) => {}
-                Err(TrySendError::Full(request)) => {
-                    warn!(
-                        "Couldn't send a {} to {}: the send channel is full",
-                        request, target_addr
-                    );
+            Ok(channel) => {
+                let lane = channel.lane(priority);
+
+                if priority == Priority::Critical {
+                    // Bounded blocking backpressure: wait for room rather than dropping.
+                    if let Err(error) = lane.send(request).await {
+                        error!("Couldn't send a {} to {}: the send channel is closed", error.0, target_addr);
+                    }
+                    return;
                 }
-                Err(TrySendError::Closed(request)) => {
-                    error!(
-                        "Couldn't send a {} to {}: the send channel is closed",
-                        request, target_addr
-                    );
+
+                match lane.try_send(request) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(request)) => {
+                        warn!(
+                            "Couldn't send a {} to {}: the send channel is full",
+                            request, target_addr
+                        );
+                    }
+                    Err(TrySendError::Closed(request)) => {
+                        error!(
+                            "Couldn't send a {} to {}: the send channel is closed",
+                            request, target_addr
+                        );
+                    }
                 }
-            },
+            }
             Err(_) => {
                 warn!("Failed to send a {}: peer is disconnected", request);
             }
@@ -81,7 +308,7 @@ impl Outbound {
     /// Establishes an outbound channel to the given remote address, if it does not exist.
     ///
     #[inline]
-    async fn outbound_channel(&self, remote_address: SocketAddr) -> Result<Sender, NetworkError> {
+    async fn outbound_channel(&self, remote_address: SocketAddr) -> Result<PeerChannels, NetworkError> {
         Ok(self
             .channels
             .get(&remote_address)
@@ -108,20 +335,119 @@ impl<S: Storage + Send + Sync + 'static> Node<S> {
             .await;
     }
 
+    /// Builds a point-in-time snapshot of this node's network connectivity, for the
+    /// `getnetworkstats` RPC method.
+    pub async fn network_stats(&self) -> NetworkStats {
+        let (critical, normal, low) = self.outbound.lane_snapshots();
+
+        let peers = self
+            .peer_book
+            .connected_peers()
+            .into_iter()
+            .map(|address| {
+                let counts = self.outbound.channels.get(&address).map(|channel| channel.counts.snapshot()).unwrap_or_default();
+
+                PeerStats {
+                    address,
+                    last_seen: self.peer_book.last_seen(&address),
+                    block_height: self.peer_book.block_height(&address),
+                    send_success_count: counts.send_success_count,
+                    send_failure_count: counts.send_failure_count,
+                }
+            })
+            .collect();
+
+        NetworkStats {
+            connected_peer_count: self.peer_book.number_of_connected_peers(),
+            active_handshake_count: self.peer_book.number_of_connecting_peers(),
+            max_peers: self.environment.max_peers(),
+            critical,
+            normal,
+            low,
+            peers,
+        }
+    }
+
     /// This method handles new outbound messages to a single connected node.
-    pub async fn listen_for_outbound_messages(&self, mut receiver: Receiver, writer: &mut ConnWriter) {
+    ///
+    /// The three lanes are drained in strict priority order: `Critical` messages are
+    /// always taken first when available, then `Normal`, and `Low` only once the
+    /// higher lanes are empty. Each write is bounded by `Outbound::write_timeout`; a
+    /// stalled peer is torn down and scheduled for reconnection rather than left to
+    /// wedge this task indefinitely.
+    pub async fn listen_for_outbound_messages(&self, mut receivers: PeerReceivers, writer: &mut ConnWriter) {
         loop {
-            // Read the next message queued to be sent.
-            if let Some(message) = receiver.recv().await {
-                match writer.write_message(&message.payload).await {
-                    Ok(_) => {
-                        self.outbound.send_success_count.fetch_add(1, Ordering::SeqCst);
-                    }
-                    Err(error) => {
-                        warn!("Failed to send a {}: {}", message, error);
-                        self.outbound.send_failure_count.fetch_add(1, Ordering::SeqCst);
-                    }
+            let message = tokio::select! {
+                biased;
+                Some(message) = receivers.critical.recv() => message,
+                Some(message) = receivers.normal.recv() => message,
+                Some(message) = receivers.low.recv() => message,
+                else => return,
+            };
+
+            let priority = Priority::of(&message.payload);
+            let counts = self.outbound.lane_counts(priority);
+            let peer_counts = &receivers.counts;
+
+            match tokio::time::timeout(self.outbound.write_timeout, writer.write_message(&message.payload)).await {
+                Ok(Ok(_)) => {
+                    counts.send_success_count.fetch_add(1, Ordering::SeqCst);
+                    peer_counts.send_success_count.fetch_add(1, Ordering::SeqCst);
+                }
+                Ok(Err(error)) => {
+                    warn!("Failed to send a {} to {}: {}", message, writer.addr, error);
+                    counts.send_failure_count.fetch_add(1, Ordering::SeqCst);
+                    peer_counts.send_failure_count.fetch_add(1, Ordering::SeqCst);
+                    self.disconnect_and_schedule_reconnect(writer.addr);
+                    return;
+                }
+                Err(_) => {
+                    warn!(
+                        "Failed to send a {} to {}: write timed out after {:?}",
+                        message, writer.addr, self.outbound.write_timeout
+                    );
+                    counts.send_failure_count.fetch_add(1, Ordering::SeqCst);
+                    peer_counts.send_failure_count.fetch_add(1, Ordering::SeqCst);
+                    self.disconnect_and_schedule_reconnect(writer.addr);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Drops the peer's outbound channels and schedules a reconnection attempt with
+    /// exponential backoff, so a single dead socket can't silently degrade outbound
+    /// throughput forever.
+    fn disconnect_and_schedule_reconnect(&self, remote_address: SocketAddr) {
+        self.outbound.channels.remove(&remote_address);
+
+        let node = self.clone();
+        tokio::spawn(async move {
+            node.reconnect_with_backoff(remote_address).await;
+        });
+    }
+
+    /// Waits out the peer's current backoff delay (1s, 2s, 4s, ... capped at
+    /// `MAX_RECONNECT_BACKOFF`, tracked per-peer in the peer book) and then retries
+    /// the connection, repeating until an attempt succeeds.
+    ///
+    /// A successful attempt only opens a TCP connection, which is dropped right away;
+    /// the handshake and the peer's outbound channels are not re-established here.
+    async fn reconnect_with_backoff(&self, remote_address: SocketAddr) {
+        loop {
+            let attempt = self.peer_book.next_reconnect_attempt(remote_address);
+            let backoff = INITIAL_RECONNECT_BACKOFF
+                .saturating_mul(1u32.checked_shl((attempt - 1).min(6)).unwrap_or(u32::MAX))
+                .min(MAX_RECONNECT_BACKOFF);
+
+            tokio::time::sleep(backoff).await;
+
+            match self.connect_to(remote_address).await {
+                Ok(_) => {
+                    self.peer_book.reset_reconnect_attempts(remote_address);
+                    return;
                 }
+                Err(error) => warn!("Reconnection attempt to {} failed: {}", remote_address, error),
             }
         }
     }
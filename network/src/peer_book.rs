@@ -0,0 +1,100 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use parking_lot::RwLock;
+use std::{collections::HashMap, net::SocketAddr, time::SystemTime};
+
+/// What the node currently knows about a single peer.
+#[derive(Debug, Clone, Default)]
+struct PeerInfo {
+    /// `true` once any message has been received from the peer; cleared again when a
+    /// reconnection attempt is registered against it.
+    connected: bool,
+    last_seen: Option<SystemTime>,
+    /// The peer's most recently negotiated block height, if it's told us one yet.
+    block_height: Option<u32>,
+    /// How many reconnection attempts have been made since the last successful
+    /// connection, so `Node::reconnect_with_backoff` can compute an exponential delay.
+    reconnect_attempts: u32,
+}
+
+/// Tracks what this node knows about every peer it's connected, or has ever
+/// attempted to connect, to.
+#[derive(Debug, Default)]
+pub struct PeerBook {
+    peers: RwLock<HashMap<SocketAddr, PeerInfo>>,
+}
+
+impl PeerBook {
+    /// Records that a `Ping` was just sent to `remote_address`, registering it as a
+    /// known peer if it isn't one already.
+    pub fn sending_ping(&self, remote_address: SocketAddr) {
+        self.peers.write().entry(remote_address).or_default();
+    }
+
+    /// Records that a message was just received from `remote_address`: it's now
+    /// considered connected, and its last-seen time and block height are refreshed.
+    pub fn received_message(&self, remote_address: SocketAddr, block_height: u32) {
+        let mut peers = self.peers.write();
+        let info = peers.entry(remote_address).or_default();
+        info.connected = true;
+        info.last_seen = Some(SystemTime::now());
+        info.block_height = Some(block_height);
+    }
+
+    pub fn connected_peers(&self) -> Vec<SocketAddr> {
+        self.peers
+            .read()
+            .iter()
+            .filter(|(_, info)| info.connected)
+            .map(|(address, _)| *address)
+            .collect()
+    }
+
+    pub fn last_seen(&self, remote_address: &SocketAddr) -> Option<SystemTime> {
+        self.peers.read().get(remote_address).and_then(|info| info.last_seen)
+    }
+
+    pub fn block_height(&self, remote_address: &SocketAddr) -> Option<u32> {
+        self.peers.read().get(remote_address).and_then(|info| info.block_height)
+    }
+
+    pub fn number_of_connected_peers(&self) -> u16 {
+        self.peers.read().values().filter(|info| info.connected).count() as u16
+    }
+
+    pub fn number_of_connecting_peers(&self) -> u16 {
+        self.peers.read().values().filter(|info| !info.connected).count() as u16
+    }
+
+    /// Registers another reconnection attempt against `remote_address` and returns
+    /// how many (including this one) have been made since the last success, so the
+    /// caller can size its backoff.
+    pub fn next_reconnect_attempt(&self, remote_address: SocketAddr) -> u32 {
+        let mut peers = self.peers.write();
+        let info = peers.entry(remote_address).or_default();
+        info.connected = false;
+        info.reconnect_attempts += 1;
+        info.reconnect_attempts
+    }
+
+    /// Clears the reconnection-attempt counter after a successful reconnection.
+    pub fn reset_reconnect_attempts(&self, remote_address: SocketAddr) {
+        if let Some(info) = self.peers.write().get_mut(&remote_address) {
+            info.reconnect_attempts = 0;
+        }
+    }
+}
@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
 
-use snarkos_consensus::{Consensus, ConsensusParameters, MerkleTreeLedger};
+use snarkos_consensus::{Consensus, ConsensusParameters, ForkSchedule, MerkleTreeLedger};
 use snarkos_storage::{Ledger, LedgerStorage};
 use snarkvm_algorithms::{merkle_tree::MerkleTree, traits::LoadableMerkleParameters, MerkleParameters, CRH};
 use snarkvm_dpc::{
@@ -80,6 +80,9 @@ pub fn generate<S: Storage>(recipient: &str, value: u64, network_id: u8, file_na
         network_id: Network::from_network_id(network_id),
         verifier: PoswMarlin::verify_only().expect("could not instantiate PoSW verifier"),
         authorized_inner_snark_ids: vec![],
+        // This tool only ever mints a single ad-hoc transaction, so there's no
+        // height-scheduled upgrade to describe: the base rules apply from height 0 on.
+        fork_schedule: ForkSchedule::new(vec![]),
     };
     let public_parameters = <InstantiatedDPC as DPCScheme<MerkleTreeLedger<S>>>::NetworkParameters::load(false)?;
 
@@ -165,7 +168,12 @@ pub fn generate<S: Storage>(recipient: &str, value: u64, network_id: u8, file_na
 
     let memo: [u8; 32] = rng.gen();
 
-    // Generate the transaction
+    // Generate the transaction.
+    //
+    // This tool mints directly from dummy genesis-style inputs, so there's no real
+    // value to take a fee out of.
+    let fee = 0u64;
+
     let (records, transaction) = consensus
         .create_transaction(
             old_records,
@@ -177,6 +185,7 @@ pub fn generate<S: Storage>(recipient: &str, value: u64, network_id: u8, file_na
             new_values,
             new_payloads,
             memo,
+            fee,
             rng,
         )
         .unwrap();
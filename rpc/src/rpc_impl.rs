@@ -0,0 +1,82 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the snarkOS library.
+
+// The snarkOS library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The snarkOS library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the snarkOS library. If not, see <https://www.gnu.org/licenses/>.
+
+use snarkos_network::{NetworkStats, Node};
+use snarkvm_objects::Storage;
+
+use serde::Serialize;
+use std::{net::SocketAddr, time::SystemTime};
+
+/// The wire format of a single peer entry in a `getnetworkstats` response.
+#[derive(Debug, Clone, Serialize)]
+pub struct PeerInfoResponse {
+    pub address: SocketAddr,
+    pub last_seen: Option<SystemTime>,
+    pub block_height: Option<u32>,
+    pub send_success_count: u64,
+    pub send_failure_count: u64,
+}
+
+/// The wire format of a `getnetworkstats` response.
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkStatsResponse {
+    pub connected_peer_count: u16,
+    pub active_handshake_count: u16,
+    pub max_peers: u16,
+    pub send_success_count: u64,
+    pub send_failure_count: u64,
+    pub peers: Vec<PeerInfoResponse>,
+}
+
+impl From<NetworkStats> for NetworkStatsResponse {
+    fn from(stats: NetworkStats) -> Self {
+        Self {
+            connected_peer_count: stats.connected_peer_count,
+            active_handshake_count: stats.active_handshake_count,
+            max_peers: stats.max_peers,
+            send_success_count: stats.critical.send_success_count + stats.normal.send_success_count + stats.low.send_success_count,
+            send_failure_count: stats.critical.send_failure_count + stats.normal.send_failure_count + stats.low.send_failure_count,
+            peers: stats
+                .peers
+                .into_iter()
+                .map(|peer| PeerInfoResponse {
+                    address: peer.address,
+                    last_seen: peer.last_seen,
+                    block_height: peer.block_height,
+                    send_success_count: peer.send_success_count,
+                    send_failure_count: peer.send_failure_count,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// The RPC handler for node-level endpoints, backed by a `Node`.
+pub struct RpcImpl<S: Storage + Send + Sync + 'static> {
+    node: Node<S>,
+}
+
+impl<S: Storage + Send + Sync + 'static> RpcImpl<S> {
+    pub fn new(node: Node<S>) -> Self {
+        Self { node }
+    }
+
+    /// `getnetworkstats`: connectivity and outbound send health, node-wide and
+    /// per-peer, for operators diagnosing asymmetric connectivity.
+    pub async fn get_network_stats(&self) -> NetworkStatsResponse {
+        self.node.network_stats().await.into()
+    }
+}
@@ -17,10 +17,11 @@
 #[macro_use]
 extern crate tracing;
 
-use snarkos_consensus::{error::ConsensusError, Consensus, Miner};
+use snarkos_consensus::{consensus::FEE_RATE_SCALE, error::ConsensusError, Consensus, Miner};
 use snarkos_testing::sync::*;
 use snarkvm_dpc::{
     block::Transactions as DPCTransactions,
+    block_header::BlockHeader,
     testnet1::{
         instantiated::*,
         record::{payload::Payload as RecordPayload, Record as DPCRecord},
@@ -28,9 +29,11 @@ use snarkvm_dpc::{
     Account,
     AccountAddress,
     Block,
+    BlockHeaderHash,
     ProgramScheme,
     RecordScheme,
     Storage,
+    TransactionScheme,
 };
 use tracing_subscriber::EnvFilter;
 
@@ -62,8 +65,8 @@ fn mine_block<S: Storage>(
     Ok((block, coinbase_records))
 }
 
-/// Spends some value from inputs owned by the sender, to the receiver,
-/// and pays back whatever we are left with.
+/// Spends some value from inputs owned by the sender, to the receiver, pays the given
+/// fee to the miner, and pays back whatever we are left with.
 #[allow(clippy::too_many_arguments)]
 fn send<R: Rng, S: Storage>(
     consensus: &Consensus<S>,
@@ -71,6 +74,7 @@ fn send<R: Rng, S: Storage>(
     inputs: Vec<DPCRecord<Components>>,
     receiver: &AccountAddress<Components>,
     amount: u64,
+    fee: u64,
     rng: &mut R,
     memo: [u8; 32],
 ) -> Result<(Vec<DPCRecord<Components>>, Tx), ConsensusError> {
@@ -78,8 +82,8 @@ fn send<R: Rng, S: Storage>(
     for inp in &inputs {
         sum += inp.value();
     }
-    assert!(sum >= amount, "not enough balance in inputs");
-    let change = sum - amount;
+    assert!(sum >= amount + fee, "not enough balance in inputs to cover the amount and fee");
+    let change = sum - amount - fee;
 
     let input_programs = vec![FIXTURE.program.into_compact_repr(); NUM_INPUT_RECORDS];
     let output_programs = vec![FIXTURE.program.into_compact_repr(); NUM_OUTPUT_RECORDS];
@@ -100,6 +104,7 @@ fn send<R: Rng, S: Storage>(
         values,
         output,
         memo,
+        fee,
         rng,
     )
 }
@@ -126,13 +131,16 @@ fn mine_blocks(n: u32) -> Result<TestBlocks, ConsensusError> {
         txs.clear();
         let mut memo = [0u8; 32];
         memo[0] = i as u8;
-        // make a tx which spends 10 to the BaseDPCComponents receiver
+        // make a tx which spends 10 + i to the BaseDPCComponents receiver, with a fee that
+        // varies block-to-block so the logged fee history has more than one value.
+        let fee = 1 + (i as u64 % 5);
         let (_records, tx) = send(
             &consensus,
             &miner_acc,
             coinbase_records.clone(),
             &acc_1.address,
             (10 + i).into(),
+            fee,
             &mut rng,
             memo,
         )?;
@@ -141,9 +149,141 @@ fn mine_blocks(n: u32) -> Result<TestBlocks, ConsensusError> {
         blocks.push(block);
     }
 
+    log_fee_history(&consensus, blocks.len() as u32, &[0.5, 0.9]);
+
     Ok(TestBlocks::new(blocks))
 }
 
+/// Describes a single competing branch to mine for a reorg test fixture.
+#[derive(Debug, Clone, Copy)]
+pub struct Fork {
+    /// The main-chain height to branch off from; the fork's first block extends the
+    /// block already mined at this height.
+    pub fork_height: u32,
+    /// How many competing blocks to mine on this branch.
+    pub length: u32,
+}
+
+/// The output of [`mine_reorg_scenario`]: the original linear chain, one competing
+/// block set per requested [`Fork`], and the tip the heaviest of them should settle
+/// on once every set has been submitted to a consensus instance via `receive_block`.
+pub struct ReorgScenario {
+    pub main_chain: TestBlocks,
+    pub forks: Vec<TestBlocks>,
+    pub expected_tip: BlockHeaderHash,
+}
+
+/// Mines a linear main chain of `main_length` blocks, then mines one competing branch
+/// per entry in `forks`, each rooted at the main chain block at `fork.fork_height`.
+///
+/// Fork blocks are mined empty (coinbase only): a competing miner forking off an
+/// earlier height hasn't seen the main chain's later transactions, so there is nothing
+/// uncontroversial left for it to include. Unlike `mine_block`, fork blocks are never
+/// submitted to this function's own consensus instance — they're only mined and
+/// serialized, so that a consumer test can submit each set to its own consensus and
+/// assert it reorgs onto whichever branch ends up heaviest (here: tallest, since block
+/// time is held constant).
+fn mine_reorg_scenario(main_length: u32, forks: &[Fork]) -> Result<ReorgScenario, ConsensusError> {
+    info!("Creating test account");
+    let [miner_acc, acc_1, _] = FIXTURE.test_accounts.clone();
+    let mut rng = FIXTURE.rng.clone();
+    info!("Creating sync");
+    let consensus = Arc::new(crate::create_test_consensus());
+
+    info!("Creating miner");
+    let miner = Miner::new(miner_acc.address.clone(), consensus.clone());
+
+    let mut txs = vec![];
+    let mut main_blocks = vec![];
+    let mut headers_by_height = vec![];
+
+    for i in 0..main_length {
+        let (block, coinbase_records) = mine_block(&miner, txs.clone())?;
+        headers_by_height.push(block.header.clone());
+
+        txs.clear();
+        let mut memo = [0u8; 32];
+        memo[0] = i as u8;
+        let fee = 1 + (i as u64 % 5);
+        let (_records, tx) = send(
+            &consensus,
+            &miner_acc,
+            coinbase_records.clone(),
+            &acc_1.address,
+            (10 + i).into(),
+            fee,
+            &mut rng,
+            memo,
+        )?;
+
+        txs.push(tx);
+        main_blocks.push(block);
+    }
+
+    log_fee_history(&consensus, main_blocks.len() as u32, &[0.5, 0.9]);
+
+    let mut expected_tip_height = main_length;
+    let mut expected_tip_header = main_blocks
+        .last()
+        .expect("main_length must be greater than zero")
+        .header
+        .clone();
+
+    let mut fork_chains = Vec::with_capacity(forks.len());
+    for fork in forks {
+        // `headers_by_height[i]` holds the block mined at height `i + 1` (the main
+        // chain's first mined block, at index 0, is height 1), so a fork rooted at
+        // height `fork_height` branches off index `fork_height - 1`.
+        let mut previous_header = headers_by_height
+            .get((fork.fork_height as usize).saturating_sub(1))
+            .cloned()
+            .unwrap_or_else(|| panic!("fork_height {} is beyond the mined main chain", fork.fork_height));
+
+        let mut fork_blocks = Vec::with_capacity(fork.length as usize);
+        for _ in 0..fork.length {
+            let header = miner.find_block(&DPCTransactions(vec![]), &previous_header)?;
+            let block = Block {
+                header: header.clone(),
+                transactions: DPCTransactions(vec![]),
+            };
+            previous_header = header;
+            fork_blocks.push(block);
+        }
+
+        let fork_tip_height = fork.fork_height + fork.length;
+        if fork_tip_height > expected_tip_height {
+            expected_tip_height = fork_tip_height;
+            expected_tip_header = fork_blocks
+                .last()
+                .expect("fork.length must be greater than zero")
+                .header
+                .clone();
+        }
+
+        fork_chains.push(TestBlocks::new(fork_blocks));
+    }
+
+    Ok(ReorgScenario {
+        main_chain: TestBlocks::new(main_blocks),
+        forks: fork_chains,
+        expected_tip: expected_tip_header.get_hash(),
+    })
+}
+
+/// Logs a fee-history summary of the last `n_blocks` of `consensus.ledger`, oldest to
+/// newest, via the real `Consensus::get_fee_history`.
+fn log_fee_history<S: Storage>(consensus: &Consensus<S>, n_blocks: u32, percentiles: &[f64]) {
+    let history = consensus.get_fee_history(n_blocks, percentiles).expect("could not compute fee history");
+
+    for entry in history.entries {
+        info!(
+            "height {}: total_fees = {}, fullness = {:.4}, fee/byte percentiles (x{}) = {:?}",
+            entry.height, entry.total_fees, entry.block_fullness, FEE_RATE_SCALE, entry.fee_per_byte_percentiles
+        );
+    }
+    info!("suggested base fee/byte (x{}) = {}", FEE_RATE_SCALE, history.suggested_base_fee_per_byte);
+}
+
 pub fn main() {
     let filter = EnvFilter::from_default_env().add_directive("tokio_reactor=off".parse().unwrap());
     tracing_subscriber::fmt()
@@ -160,4 +300,23 @@ pub fn main() {
         File::create(PathBuf::from(format!("test_blocks_{}", block_count))).expect("could not open file"),
     );
     test_blocks.write(file).expect("could not write to file");
+
+    info!("Setting up a reorg scenario");
+    // Fork off height 40 and mine 15 competing blocks, so the fork's tip (height 55)
+    // passes the main chain's tip (height 50).
+    let scenario = mine_reorg_scenario(50, &[Fork { fork_height: 40, length: 15 }]).unwrap();
+
+    let main_file = std::io::BufWriter::new(
+        File::create(PathBuf::from("test_blocks_reorg_main")).expect("could not open file"),
+    );
+    scenario.main_chain.write(main_file).expect("could not write to file");
+
+    for (i, fork) in scenario.forks.into_iter().enumerate() {
+        let fork_file = std::io::BufWriter::new(
+            File::create(PathBuf::from(format!("test_blocks_reorg_fork_{}", i))).expect("could not open file"),
+        );
+        fork.write(fork_file).expect("could not write to file");
+    }
+
+    info!("Expected canonical tip after replay: {:?}", scenario.expected_tip);
 }